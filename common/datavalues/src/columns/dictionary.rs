@@ -0,0 +1,87 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::data_column::DataColumn;
+use crate::DFUInt32Array;
+use crate::Series;
+
+/// A low-cardinality column: a `keys` array of indices into a much smaller
+/// `values` array of distinct entries. Mirrors dictionary-backed column
+/// storage used to cut memory for repetitive string/enum columns.
+///
+/// Operations that can be expressed purely over `values` (e.g. arithmetic
+/// kernels) should run on it directly and only re-expand the per-row result
+/// through `keys`, rather than densifying `values` into a full-length array
+/// up front.
+#[derive(Clone)]
+pub struct DataColumnDictionary {
+    keys: DFUInt32Array,
+    values: Arc<Series>,
+}
+
+impl DataColumnDictionary {
+    pub fn try_create(keys: DFUInt32Array, values: Series) -> Result<Self> {
+        let max_key = keys.into_no_null_iter().max().unwrap_or(0) as usize;
+        if max_key >= values.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "dictionary key {} out of bounds for {} distinct values",
+                max_key,
+                values.len()
+            )));
+        }
+        Ok(Self {
+            keys,
+            values: Arc::new(values),
+        })
+    }
+
+    pub fn keys(&self) -> &DFUInt32Array {
+        &self.keys
+    }
+
+    pub fn values(&self) -> &Series {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Expands the dictionary into a full-length, densified column. Used as
+    /// a fallback whenever an operation cannot be expressed over the
+    /// dictionary's `values` domain.
+    pub fn densify(&self) -> Result<DataColumn> {
+        let taken = self.values.take(&self.keys)?;
+        Ok(DataColumn::Array(taken))
+    }
+
+    /// Builds a new dictionary that shares this one's `keys` but replaces
+    /// `values` with the per-distinct-value result of an operation already
+    /// evaluated over `values` alone (e.g. a binary arithmetic kernel).
+    pub fn with_values(&self, values: Series) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            values: Arc::new(values),
+        }
+    }
+}
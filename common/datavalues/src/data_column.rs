@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::columns::DataColumnDictionary;
+use crate::DataValue;
+use crate::Series;
+
+/// A column's in-memory representation: either fully materialized, a single
+/// repeated value, or dictionary-encoded (a small `values` domain addressed
+/// by a `keys` array of indices).
+#[derive(Clone)]
+pub enum DataColumn {
+    Array(Series),
+    Constant(DataValue, usize),
+    Dictionary(DataColumnDictionary),
+}
+
+impl From<DataColumnDictionary> for DataColumn {
+    fn from(dictionary: DataColumnDictionary) -> Self {
+        DataColumn::Dictionary(dictionary)
+    }
+}
+
+impl From<Series> for DataColumn {
+    fn from(series: Series) -> Self {
+        DataColumn::Array(series)
+    }
+}
@@ -29,14 +29,75 @@ use crate::scalars::Monotonicity;
 use crate::try_binary_arithmetic_helper;
 use crate::with_match_primitive_type;
 
+/// How `ArithmeticIntDiv` (and friends) should treat a zero divisor.
+///
+/// Carried explicitly in a [`FunctionContext`] from the session that's
+/// planning the query down to `ArithmeticIntDivFunction::try_create_func`,
+/// which bakes the chosen behaviour into the monomorphized kernel it
+/// builds. This used to live in a `thread_local!`, but a session's work can
+/// hop tokio worker threads between polls, so "the thread creating this
+/// function" is not reliably "the thread that set the session's policy" —
+/// the value has to be threaded through the call instead of read from
+/// ambient state.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DivideByZeroMode {
+    /// Current behaviour: abort the whole batch with a `BadArguments` error.
+    Error,
+    /// ANSI-style: the offending row becomes `NULL`, the rest of the batch
+    /// still computes.
+    Null,
+}
+
+impl Default for DivideByZeroMode {
+    fn default() -> Self {
+        DivideByZeroMode::Error
+    }
+}
+
+/// Per-session settings a scalar function's creator may need but which
+/// aren't derivable from the call's argument types alone — e.g. the
+/// `divide_by_zero` setting for [`ArithmeticIntDivFunction`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FunctionContext {
+    pub divide_by_zero: DivideByZeroMode,
+}
+
+/// Per-policy zero-divisor behaviour, selected at creation time so the
+/// hot division loop stays branch-free on the policy itself.
+trait DivideByZeroPolicy: Clone + Send + Sync + 'static {
+    const NULLABLE: bool;
+    fn on_zero<R>() -> Result<Option<R>>;
+}
+
+#[derive(Clone)]
+struct ErrorOnZero;
+
+impl DivideByZeroPolicy for ErrorOnZero {
+    const NULLABLE: bool = false;
+    fn on_zero<R>() -> Result<Option<R>> {
+        Err(ErrorCode::BadArguments("Division by zero"))
+    }
+}
+
+#[derive(Clone)]
+struct NullOnZero;
+
+impl DivideByZeroPolicy for NullOnZero {
+    const NULLABLE: bool = true;
+    fn on_zero<R>() -> Result<Option<R>> {
+        Ok(None)
+    }
+}
+
 #[derive(Clone)]
-pub struct ArithmeticIntDiv<T, D, R> {
+pub struct ArithmeticIntDiv<T, D, R, P = ErrorOnZero> {
     t: PhantomData<T>,
     d: PhantomData<D>,
     r: PhantomData<R>,
+    p: PhantomData<P>,
 }
 
-impl<T, D, R> ArithmeticTrait for ArithmeticIntDiv<T, D, R>
+impl<T, D, R, P> ArithmeticTrait for ArithmeticIntDiv<T, D, R, P>
 where
     f64: AsPrimitive<R>,
     T: DFPrimitiveType + AsPrimitive<f64>,
@@ -44,47 +105,169 @@ where
     R: DFPrimitiveType,
     DFPrimitiveArray<R>: IntoSeries,
     R: Into<DataValue>,
+    P: DivideByZeroPolicy,
 {
     fn arithmetic(columns: &DataColumnsWithField) -> Result<DataColumn> {
+        // Dictionary-encoded operands: compute the division once per distinct
+        // value and only re-expand the (much smaller) result through the
+        // original keys, instead of densifying the repeated values first.
+        match (columns[0].column(), columns[1].column()) {
+            (DataColumn::Dictionary(left), DataColumn::Constant(right, _)) => {
+                let rhs: D = DFTryFrom::try_from(right.clone()).unwrap_or(D::one());
+                let r: f64 = rhs.as_();
+                if r == 0.0 {
+                    if !P::NULLABLE {
+                        return Err(ErrorCode::BadArguments("Division by zero"));
+                    }
+                    // Every row divides by the same zero constant: go
+                    // straight to NULLs at the original (pre-dictionary)
+                    // cardinality, same as the plain-array zero-constant case.
+                    let result_values: DFPrimitiveArray<R> = (0..left.keys().len())
+                        .map(|_| P::on_zero::<R>())
+                        .collect::<Result<DFPrimitiveArray<R>>>()?;
+                    return Ok(DataColumn::Array(result_values.into_series()));
+                }
+                let lhs: &DFPrimitiveArray<T> = left.values().static_cast();
+                let result_values: DFPrimitiveArray<R> = unary(lhs, |l| {
+                    AsPrimitive::<R>::as_(AsPrimitive::<f64>::as_(l) / r)
+                });
+                return Ok(DataColumn::Array(result_values.take(left.keys())?.into_series()));
+            }
+            (DataColumn::Constant(left, _), DataColumn::Dictionary(right)) => {
+                let lhs: T = DFTryFrom::try_from(left.clone()).unwrap_or(T::default());
+                let l: f64 = lhs.as_();
+                let rhs: &DFPrimitiveArray<D> = right.values().static_cast();
+                let result_values: DFPrimitiveArray<R> = if !P::NULLABLE {
+                    try_unary(rhs, |r| {
+                        let r: f64 = r.as_();
+                        if std::intrinsics::unlikely(r == 0.0) {
+                            return Err(ErrorCode::BadArguments("Division by zero"));
+                        }
+                        Ok(AsPrimitive::<R>::as_(l / r))
+                    })?
+                } else {
+                    rhs.into_iter()
+                        .map(|r| match r {
+                            Some(r) => {
+                                let r: f64 = r.as_();
+                                if std::intrinsics::unlikely(r == 0.0) {
+                                    P::on_zero::<R>()
+                                } else {
+                                    Ok(Some(AsPrimitive::<R>::as_(l / r)))
+                                }
+                            }
+                            None => Ok(None),
+                        })
+                        .collect::<Result<DFPrimitiveArray<R>>>()?
+                };
+                return Ok(DataColumn::Array(result_values.take(right.keys())?.into_series()));
+            }
+            // Both-dictionary and dictionary-vs-array combinations can't be
+            // expressed purely over `values` (keys don't line up 1:1), so
+            // fall back to densifying whichever side is a dictionary.
+            (DataColumn::Dictionary(left), right) => {
+                let columns = &[
+                    DataColumnWithField::new(left.densify()?, columns[0].field().clone()),
+                    columns[1].clone(),
+                ];
+                let _ = right;
+                return Self::arithmetic(columns);
+            }
+            (left, DataColumn::Dictionary(right)) => {
+                let columns = &[
+                    columns[0].clone(),
+                    DataColumnWithField::new(right.densify()?, columns[1].field().clone()),
+                ];
+                let _ = left;
+                return Self::arithmetic(columns);
+            }
+            _ => {}
+        }
+
         let result: DataColumn = match (columns[0].column(), columns[1].column()) {
             (DataColumn::Array(left), DataColumn::Array(right)) => {
                 let lhs: &DFPrimitiveArray<T> = left.static_cast();
                 let rhs: &DFPrimitiveArray<D> = right.static_cast();
-                try_binary(lhs, rhs, |l, r| {
-                    let l: f64 = l.as_();
-                    let r: f64 = r.as_();
-                    if std::intrinsics::unlikely(r == 0.0) {
-                        return Err(ErrorCode::BadArguments("Division by zero"));
-                    }
-                    Ok(AsPrimitive::<R>::as_(l / r))
-                })?
-                .into()
+                if !P::NULLABLE {
+                    try_binary(lhs, rhs, |l, r| {
+                        let l: f64 = l.as_();
+                        let r: f64 = r.as_();
+                        if std::intrinsics::unlikely(r == 0.0) {
+                            return Err(ErrorCode::BadArguments("Division by zero"));
+                        }
+                        Ok(AsPrimitive::<R>::as_(l / r))
+                    })?
+                    .into()
+                } else {
+                    lhs.into_iter()
+                        .zip(rhs.into_iter())
+                        .map(|(l, r)| match (l, r) {
+                            (Some(l), Some(r)) => {
+                                let l: f64 = l.as_();
+                                let r: f64 = r.as_();
+                                if std::intrinsics::unlikely(r == 0.0) {
+                                    P::on_zero::<R>()
+                                } else {
+                                    Ok(Some(AsPrimitive::<R>::as_(l / r)))
+                                }
+                            }
+                            _ => Ok(None),
+                        })
+                        .collect::<Result<DFPrimitiveArray<R>>>()?
+                        .into()
+                }
             }
             (DataColumn::Array(left), DataColumn::Constant(right, _)) => {
                 let lhs: &DFPrimitiveArray<T> = left.static_cast();
                 let rhs: D = DFTryFrom::try_from(right.clone()).unwrap_or(D::one());
                 let r: f64 = rhs.as_();
-                if r == 0.0 {
+                if r == 0.0 && !P::NULLABLE {
                     return Err(ErrorCode::BadArguments("Division by zero"));
                 }
 
-                unary(lhs, |l| {
-                    AsPrimitive::<R>::as_(AsPrimitive::<f64>::as_(l) / r)
-                })
-                .into()
+                if r != 0.0 {
+                    unary(lhs, |l| {
+                        AsPrimitive::<R>::as_(AsPrimitive::<f64>::as_(l) / r)
+                    })
+                    .into()
+                } else {
+                    // Every row divides by the same zero constant: the whole
+                    // array becomes NULL (policy already confirmed nullable).
+                    lhs.into_iter()
+                        .map(|_| P::on_zero::<R>())
+                        .collect::<Result<DFPrimitiveArray<R>>>()?
+                        .into()
+                }
             }
             (DataColumn::Constant(left, _), DataColumn::Array(right)) => {
                 let lhs: T = DFTryFrom::try_from(left.clone()).unwrap_or(T::default());
                 let l: f64 = lhs.as_();
                 let rhs: &DFPrimitiveArray<D> = right.static_cast();
-                try_unary(rhs, |r| {
-                    let r: f64 = r.as_();
-                    if std::intrinsics::unlikely(r == 0.0) {
-                        return Err(ErrorCode::BadArguments("Division by zero"));
-                    }
-                    Ok(AsPrimitive::<R>::as_(l / r))
-                })?
-                .into()
+                if !P::NULLABLE {
+                    try_unary(rhs, |r| {
+                        let r: f64 = r.as_();
+                        if std::intrinsics::unlikely(r == 0.0) {
+                            return Err(ErrorCode::BadArguments("Division by zero"));
+                        }
+                        Ok(AsPrimitive::<R>::as_(l / r))
+                    })?
+                    .into()
+                } else {
+                    rhs.into_iter()
+                        .map(|r| match r {
+                            Some(r) => {
+                                let r: f64 = r.as_();
+                                if std::intrinsics::unlikely(r == 0.0) {
+                                    P::on_zero::<R>()
+                                } else {
+                                    Ok(Some(AsPrimitive::<R>::as_(l / r)))
+                                }
+                            }
+                            None => Ok(None),
+                        })
+                        .collect::<Result<DFPrimitiveArray<R>>>()?
+                        .into()
+                }
             }
             (DataColumn::Constant(left, size), DataColumn::Constant(right, _)) => {
                 let lhs: T = DFTryFrom::try_from(left.clone()).unwrap_or(T::default());
@@ -92,7 +275,10 @@ where
                 let rhs: D = DFTryFrom::try_from(right.clone()).unwrap_or(D::one());
                 let r: f64 = rhs.as_();
                 if r == 0.0 {
-                    return Err(ErrorCode::BadArguments("Division by zero"));
+                    return match P::on_zero::<R>()? {
+                        Some(v) => Ok(DataColumn::Constant(v.into(), size.clone())),
+                        None => Ok(DataColumn::Constant(DataValue::Null, size.clone())),
+                    };
                 }
                 DataColumn::Constant((AsPrimitive::<R>::as_(l / r)).into(), size.clone())
             }
@@ -105,7 +291,11 @@ where
 pub struct ArithmeticIntDivFunction;
 
 impl ArithmeticIntDivFunction {
+    /// `ctx` carries the session's `divide_by_zero` setting in explicitly —
+    /// see [`FunctionContext`] for why this isn't read from thread-local
+    /// state.
     pub fn try_create_func(
+        ctx: &FunctionContext,
         _display_name: &str,
         args: &[DataTypeAndNullable],
     ) -> Result<Box<dyn Function>> {
@@ -124,23 +314,46 @@ impl ArithmeticIntDivFunction {
             return error_fn();
         };
 
-        with_match_primitive_type!(left_type, |$T| {
-            with_match_primitive_type!(right_type, |$D| {
-                let result_type = <($T, $D) as ResultTypeOfBinary>::IntDiv::data_type();
-                BinaryArithmeticFunction::<ArithmeticIntDiv::<$T,$D, <($T, $D) as ResultTypeOfBinary>::IntDiv>>::try_create_func(
-                    op,
-                    result_type,
-                )
+        match ctx.divide_by_zero {
+            DivideByZeroMode::Error => with_match_primitive_type!(left_type, |$T| {
+                with_match_primitive_type!(right_type, |$D| {
+                    let result_type = <($T, $D) as ResultTypeOfBinary>::IntDiv::data_type();
+                    BinaryArithmeticFunction::<ArithmeticIntDiv::<$T,$D, <($T, $D) as ResultTypeOfBinary>::IntDiv, ErrorOnZero>>::try_create_func(
+                        op,
+                        result_type,
+                    )
+                }, {
+                    error_fn()
+                })
+            }, {
+                error_fn()
+            }),
+            DivideByZeroMode::Null => with_match_primitive_type!(left_type, |$T| {
+                with_match_primitive_type!(right_type, |$D| {
+                    // NullOnZero can turn any row into NULL regardless of
+                    // whether the operands are nullable, so the declared
+                    // result type has to be nullable too, or callers that
+                    // trust the schema (e.g. to skip null-handling) will be
+                    // handed a NULL they weren't told to expect.
+                    let result_type = <($T, $D) as ResultTypeOfBinary>::IntDiv::data_type().nullable();
+                    BinaryArithmeticFunction::<ArithmeticIntDiv::<$T,$D, <($T, $D) as ResultTypeOfBinary>::IntDiv, NullOnZero>>::try_create_func(
+                        op,
+                        result_type,
+                    )
+                }, {
+                    error_fn()
+                })
             }, {
                 error_fn()
-            })
-        }, {
-            error_fn()
-        })
+            }),
+        }
     }
 
-    pub fn desc() -> ArithmeticDescription {
-        ArithmeticDescription::creator(Box::new(Self::try_create_func)).features(
+    pub fn desc(ctx: FunctionContext) -> ArithmeticDescription {
+        ArithmeticDescription::creator(Box::new(move |display_name, args| {
+            Self::try_create_func(&ctx, display_name, args)
+        }))
+        .features(
             FunctionFeatures::default()
                 .deterministic()
                 .monotonicity()
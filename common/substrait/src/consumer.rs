@@ -0,0 +1,186 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataTypeAndNullable;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::ArithmeticIntDivFunction;
+use common_functions::scalars::Function;
+use common_functions::scalars::FunctionContext;
+use substrait::proto::expression::field_reference::ReferenceType;
+use substrait::proto::expression::literal::LiteralType;
+use substrait::proto::expression::reference_segment::ReferenceType as SegmentReferenceType;
+use substrait::proto::expression::RexType;
+use substrait::proto::extensions::simple_extension_declaration::MappingType;
+use substrait::proto::extensions::SimpleExtensionDeclaration;
+use substrait::proto::Expression;
+
+use crate::expr::Expr;
+
+/// A creator registered under a Substrait function name, mirroring the
+/// `FunctionFactory` creators used for SQL-parsed calls. Takes a
+/// [`FunctionContext`] so functions whose behaviour depends on session
+/// settings (e.g. `ArithmeticIntDivFunction`'s `divide_by_zero` mode) don't
+/// need to reach for ambient global state to find them.
+type FunctionCreator = fn(&FunctionContext, &str, &[DataTypeAndNullable]) -> Result<Box<dyn Function>>;
+
+/// Reconstructs Databend's [`Expr`] tree from Substrait `Expression`
+/// messages, resolving `function_reference` anchors against the plan's
+/// `extensions` — populated by [`crate::SubstraitProducer`] for a plan this
+/// crate produced itself, or by any other Substrait-speaking engine.
+pub struct SubstraitConsumer {
+    /// anchor -> function name, populated from the plan's `extensions`
+    /// before any `Expression` in the plan's `Rel`s is consumed.
+    anchors: HashMap<u32, String>,
+}
+
+impl SubstraitConsumer {
+    pub fn create() -> Self {
+        Self {
+            anchors: HashMap::new(),
+        }
+    }
+
+    /// Registers the function extensions declared at the top of a Substrait
+    /// plan, so later `function_reference`s in the plan's expressions can be
+    /// resolved without re-reading the extension list each time.
+    pub fn register_extensions(&mut self, extensions: &[SimpleExtensionDeclaration]) {
+        for ext in extensions {
+            if let Some(MappingType::ExtensionFunction(f)) = &ext.mapping_type {
+                self.anchors.insert(f.function_anchor, f.name.clone());
+            }
+        }
+    }
+
+    /// Consumes a Substrait `Expression` into Databend's [`Expr`] tree.
+    ///
+    /// There is no `Expr::Alias` or `Expr::Aggregator` to reconstruct here:
+    /// [`crate::SubstraitProducer::produce_expression`] never emits a
+    /// `rex_type` for either, since Substrait names expressions at the
+    /// relation level and models aggregates as `AggregateRel` measures, not
+    /// as anything a bare `Expression` can carry.
+    pub fn consume_expression(&self, expression: &Expression) -> Result<Expr> {
+        match &expression.rex_type {
+            Some(RexType::Literal(literal)) => Ok(Expr::Constant(Self::consume_literal(literal))),
+            Some(RexType::Selection(selection)) => {
+                Ok(Expr::Variable(Self::consume_field_reference(selection)?))
+            }
+            Some(RexType::ScalarFunction(call)) => {
+                let name = self.resolve_anchor(call.function_reference)?;
+                let args = call
+                    .arguments
+                    .iter()
+                    .map(|arg| match &arg.arg_type {
+                        Some(substrait::proto::function_argument::ArgType::Value(value)) => {
+                            self.consume_expression(value)
+                        }
+                        other => Err(ErrorCode::UnImplement(format!(
+                            "unsupported Substrait function argument: {:?}",
+                            other
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::classify_scalar_call(name, args))
+            }
+            other => Err(ErrorCode::UnImplement(format!(
+                "unsupported Substrait expression for consumption: {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Materializes a consumed [`Expr::Arithmetic`] node into an actual
+    /// function via the crate's `FunctionFactory` creators, e.g.
+    /// `ArithmeticIntDivFunction::try_create_func`. `ctx` is the requesting
+    /// session's [`FunctionContext`] — the plan carries no session settings
+    /// of its own, so the caller (whoever is replaying this plan) supplies
+    /// it the same way it would for a SQL-parsed call.
+    pub fn to_function(
+        ctx: &FunctionContext,
+        expr: &Expr,
+        args: &[DataTypeAndNullable],
+    ) -> Result<Box<dyn Function>> {
+        let name = match expr {
+            Expr::Arithmetic(name, _) | Expr::Comparison(name, _) | Expr::Logic(name, _) => name,
+            other => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "{:?} is not a scalar function call",
+                    other
+                )))
+            }
+        };
+        let creator = Self::lookup_creator(name).ok_or_else(|| {
+            ErrorCode::LogicalError(format!("no FunctionFactory creator registered for '{}'", name))
+        })?;
+        creator(ctx, name, args)
+    }
+
+    fn lookup_creator(name: &str) -> Option<FunctionCreator> {
+        match name {
+            "div" | "intdiv" => Some(ArithmeticIntDivFunction::try_create_func),
+            _ => None,
+        }
+    }
+
+    fn resolve_anchor(&self, anchor: u32) -> Result<&str> {
+        self.anchors
+            .get(&anchor)
+            .map(String::as_str)
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!("no function registered for anchor {}", anchor))
+            })
+    }
+
+    /// Substrait doesn't distinguish arithmetic/comparison/logic scalar
+    /// calls by anchor the way `Expr` does; classify by the well-known
+    /// function name instead, defaulting unrecognized names to `Arithmetic`.
+    fn classify_scalar_call(name: &str, args: Vec<Expr>) -> Expr {
+        match name {
+            "eq" | "lt" | "lte" | "gt" | "gte" | "neq" => Expr::Comparison(name.to_string(), args),
+            "and" | "or" | "not" => Expr::Logic(name.to_string(), args),
+            _ => Expr::Arithmetic(name.to_string(), args),
+        }
+    }
+
+    fn consume_literal(literal: &substrait::proto::expression::Literal) -> DataValue {
+        match &literal.literal_type {
+            Some(LiteralType::Boolean(v)) => DataValue::Boolean(*v),
+            Some(LiteralType::I64(v)) => DataValue::Int64(*v),
+            Some(LiteralType::Fp64(v)) => DataValue::Float64(*v),
+            Some(LiteralType::String(v)) => DataValue::String(v.clone().into_bytes()),
+            _ => DataValue::Null,
+        }
+    }
+
+    fn consume_field_reference(
+        selection: &substrait::proto::expression::FieldReference,
+    ) -> Result<usize> {
+        match &selection.reference_type {
+            Some(ReferenceType::DirectReference(segment)) => match &segment.reference_type {
+                Some(SegmentReferenceType::StructField(field)) => Ok(field.field as usize),
+                other => Err(ErrorCode::UnImplement(format!(
+                    "unsupported Substrait reference segment: {:?}",
+                    other
+                ))),
+            },
+            other => Err(ErrorCode::UnImplement(format!(
+                "unsupported Substrait field reference: {:?}",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataValue;
+
+/// Databend's internal expression tree, as seen by the Substrait producer
+/// and consumer. Named after, and structurally mirroring, the crate's
+/// `Function` enum (`Alias`, `Constant`, `Variable`, `Arithmetic`,
+/// `Comparison`, `Logic`, `Aggregator`), but kept local to `common-substrait`
+/// so this crate never depends on the `fuse_query` binary crate.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Alias(String, Box<Expr>),
+    Constant(DataValue),
+    /// Index of the referenced column in the input schema.
+    Variable(usize),
+    Arithmetic(String, Vec<Expr>),
+    Comparison(String, Vec<Expr>),
+    Logic(String, Vec<Expr>),
+    Aggregator(String, Vec<Expr>),
+}
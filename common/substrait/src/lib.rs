@@ -0,0 +1,25 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Translates between Databend's internal expression/function tree and
+//! [Substrait](https://substrait.io) `Rel`/`Expression` protobuf messages,
+//! so logical plans can be exchanged with other query engines.
+
+mod consumer;
+mod expr;
+mod producer;
+
+pub use consumer::SubstraitConsumer;
+pub use expr::Expr;
+pub use producer::SubstraitProducer;
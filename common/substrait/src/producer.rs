@@ -0,0 +1,177 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use substrait::proto::expression::field_reference::ReferenceType;
+use substrait::proto::expression::reference_segment::ReferenceType as SegmentReferenceType;
+use substrait::proto::expression::scalar_function::Arg;
+use substrait::proto::expression::FieldReference;
+use substrait::proto::expression::Literal;
+use substrait::proto::expression::ReferenceSegment;
+use substrait::proto::expression::RexType;
+use substrait::proto::expression::ScalarFunction;
+use substrait::proto::extensions::simple_extension_declaration::ExtensionFunction;
+use substrait::proto::extensions::simple_extension_declaration::MappingType;
+use substrait::proto::extensions::SimpleExtensionDeclaration;
+use substrait::proto::function_argument::ArgType;
+use substrait::proto::Expression;
+use substrait::proto::FunctionArgument;
+
+use crate::expr::Expr;
+
+/// Walks an [`Expr`] tree and emits the matching Substrait scalar function
+/// reference plus its argument expressions. Aggregate functions are not
+/// produced: `Expr::Aggregator` is rejected (see [`Self::produce_expression`]).
+///
+/// Every scalar function name used gets exactly one anchor,
+/// recorded once in [`Self::extensions`]; [`crate::SubstraitConsumer`]
+/// resolves `function_reference`s back to a name through that same list, so
+/// producing the extension declarations is what makes a produced plan
+/// round-trip.
+#[derive(Default)]
+pub struct SubstraitProducer {
+    anchors: HashMap<String, u32>,
+    extensions: Vec<SimpleExtensionDeclaration>,
+}
+
+impl SubstraitProducer {
+    pub fn create() -> Self {
+        Self::default()
+    }
+
+    /// The `extensions` a produced plan must carry alongside the
+    /// `Expression`s this producer emitted, so the consumer can resolve
+    /// `function_reference` anchors back to function names.
+    pub fn extensions(&self) -> &[SimpleExtensionDeclaration] {
+        &self.extensions
+    }
+
+    /// Produces a Substrait `Expression` for one node of the expression tree.
+    ///
+    /// Two scope cuts, both because a bare Substrait `Expression` has no
+    /// carrier for the thing being asked of it:
+    /// - `Expr::Alias` has its name dropped. Substrait only names
+    ///   expressions at the enclosing relation's `emit`/output-field level,
+    ///   not on the expression itself, so there is nowhere in an
+    ///   `Expression` to put it. A caller that needs the alias preserved
+    ///   has to carry it at the `Rel` level once this crate produces those
+    ///   (it doesn't yet — see `Expr::Aggregator` below).
+    /// - `Expr::Aggregator` is rejected outright: aggregate functions are
+    ///   Substrait `AggregateRel` measures, not scalar `Expression`s, and
+    ///   this crate doesn't produce `Rel`s yet, only bare `Expression`
+    ///   trees. Consuming the same plan back hits the same wall (see
+    ///   `SubstraitConsumer::consume_expression`).
+    pub fn produce_expression(&mut self, expr: &Expr) -> Result<Expression> {
+        match expr {
+            Expr::Alias(_name, inner) => self.produce_expression(inner),
+            Expr::Constant(value) => Ok(Expression {
+                rex_type: Some(RexType::Literal(Self::produce_literal(value)?)),
+            }),
+            Expr::Variable(index) => Ok(Expression {
+                rex_type: Some(RexType::Selection(Box::new(Self::produce_field_reference(
+                    *index,
+                )))),
+            }),
+            Expr::Arithmetic(name, args) => self.produce_scalar_call(name, args),
+            Expr::Comparison(name, args) => self.produce_scalar_call(name, args),
+            Expr::Logic(name, args) => self.produce_scalar_call(name, args),
+            Expr::Aggregator(name, _args) => Err(ErrorCode::UnImplement(format!(
+                "aggregate function '{}' must be produced as a Substrait AggregateRel \
+                 measure, not a scalar Expression",
+                name
+            ))),
+        }
+    }
+
+    fn produce_scalar_call(&mut self, name: &str, args: &[Expr]) -> Result<Expression> {
+        let anchor = self.function_anchor(name);
+        let arguments = args
+            .iter()
+            .map(|arg| {
+                Ok(FunctionArgument {
+                    arg_type: Some(ArgType::Value(self.produce_expression(arg)?)),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Expression {
+            rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+                function_reference: anchor,
+                arguments,
+                args: Vec::<Arg>::new(),
+                ..Default::default()
+            })),
+        })
+    }
+
+    /// Returns the anchor for `name`, assigning and recording a new one (via
+    /// a `SimpleExtensionDeclaration`) the first time this producer sees it.
+    fn function_anchor(&mut self, name: &str) -> u32 {
+        if let Some(anchor) = self.anchors.get(name) {
+            return *anchor;
+        }
+        let anchor = self.anchors.len() as u32;
+        self.anchors.insert(name.to_string(), anchor);
+        self.extensions.push(SimpleExtensionDeclaration {
+            mapping_type: Some(MappingType::ExtensionFunction(ExtensionFunction {
+                function_anchor: anchor,
+                name: name.to_string(),
+                ..Default::default()
+            })),
+        });
+        anchor
+    }
+
+    fn produce_literal(value: &DataValue) -> Result<Literal> {
+        use substrait::proto::expression::literal::LiteralType;
+
+        let literal_type = match value {
+            DataValue::Null => return Ok(Literal {
+                nullable: true,
+                literal_type: None,
+                ..Default::default()
+            }),
+            DataValue::Boolean(v) => LiteralType::Boolean(*v),
+            DataValue::Int64(v) => LiteralType::I64(*v),
+            DataValue::UInt64(v) => LiteralType::I64(*v as i64),
+            DataValue::Float64(v) => LiteralType::Fp64(*v),
+            DataValue::String(v) => {
+                LiteralType::String(String::from_utf8_lossy(v).into_owned())
+            }
+        };
+        Ok(Literal {
+            nullable: false,
+            literal_type: Some(literal_type),
+            ..Default::default()
+        })
+    }
+
+    fn produce_field_reference(index: usize) -> FieldReference {
+        FieldReference {
+            reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
+                reference_type: Some(SegmentReferenceType::StructField(Box::new(
+                    substrait::proto::expression::reference_segment::StructField {
+                        field: index as i32,
+                        child: None,
+                    },
+                ))),
+            })),
+            root_type: None,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// A Parquet split-block bloom filter (SBBF), as embedded per column chunk
+/// by writers that follow the standard Parquet bloom filter spec.
+///
+/// Each block is 256 bits, laid out as eight 32-bit words. A value's 64-bit
+/// xxHash picks its block; the low 32 bits of the hash then set one
+/// candidate bit per word, one for each of eight fixed odd multipliers.
+pub struct SplitBlockBloomFilter {
+    blocks: Vec<[u32; Self::WORDS_PER_BLOCK]>,
+}
+
+impl SplitBlockBloomFilter {
+    const WORDS_PER_BLOCK: usize = 8;
+
+    /// Salt used to spread a hash's low 32 bits across the eight words of a
+    /// block, per the Parquet SBBF specification.
+    const SALT: [u32; Self::WORDS_PER_BLOCK] = [
+        0x47b6137b, 0x44974d91, 0x8824ad5b, 0xa2b7289d, 0x705495c7, 0x2df1424c, 0x9efc4947,
+        0x5c6bfb31,
+    ];
+
+    /// Parses the raw bytes of a bare SBBF bitset into its 256-bit blocks.
+    /// The bytes must not include a `BloomFilterHeader`; none is skipped.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % (Self::WORDS_PER_BLOCK * 4) != 0 {
+            return Err(ErrorCode::ParquetError(
+                "split-block bloom filter byte length is not a multiple of the 256-bit block size"
+                    .to_string(),
+            ));
+        }
+        let blocks = bytes
+            .chunks_exact(Self::WORDS_PER_BLOCK * 4)
+            .map(|block| {
+                let mut words = [0u32; Self::WORDS_PER_BLOCK];
+                for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+                    *word = u32::from_le_bytes(chunk.try_into().unwrap());
+                }
+                words
+            })
+            .collect();
+        Ok(Self { blocks })
+    }
+
+    /// Returns `true` if `hash` (the 64-bit xxHash of the probed value)
+    /// *might* be present, and `false` if it is definitely absent.
+    pub fn might_contain(&self, hash: u64) -> bool {
+        if self.blocks.is_empty() {
+            return true;
+        }
+        let block_idx = (((hash >> 32) * self.blocks.len() as u64) >> 32) as usize;
+        let block = &self.blocks[block_idx];
+        let lo = (hash & 0xFFFF_FFFF) as u32;
+
+        Self::SALT.iter().enumerate().all(|(i, salt)| {
+            let bit = salt.wrapping_mul(lo) >> 27;
+            block[i] & (1u32 << bit) != 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_cannot_prune() {
+        let filter = SplitBlockBloomFilter::from_bytes(&[]).unwrap();
+        assert!(filter.might_contain(0x1234_5678_9abc_def0));
+    }
+
+    #[test]
+    fn all_bits_set_always_matches() {
+        let bytes = vec![0xFFu8; 32];
+        let filter = SplitBlockBloomFilter::from_bytes(&bytes).unwrap();
+        assert!(filter.might_contain(42));
+        assert!(filter.might_contain(u64::MAX));
+    }
+
+    #[test]
+    fn all_bits_clear_never_matches() {
+        let bytes = vec![0u8; 32];
+        let filter = SplitBlockBloomFilter::from_bytes(&bytes).unwrap();
+        assert!(!filter.might_contain(42));
+    }
+
+    #[test]
+    fn rejects_misaligned_byte_length() {
+        assert!(SplitBlockBloomFilter::from_bytes(&[0u8; 10]).is_err());
+    }
+}
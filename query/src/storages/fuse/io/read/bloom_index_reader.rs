@@ -27,8 +27,10 @@ use common_arrow::parquet::read::PageReader;
 use common_cache::Cache;
 use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
+use common_arrow::parquet::schema::types::PhysicalType;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
 use common_exception::ErrorCode;
@@ -37,6 +39,8 @@ use common_tracing::tracing;
 use futures_util::future::try_join_all;
 use opendal::Operator;
 
+use super::bloom_filter_sbbf::SplitBlockBloomFilter;
+
 #[tracing::instrument(level = "debug", skip_all)]
 pub async fn load_bloom_filter_by_columns(
     ctx: &Arc<dyn TableContext>,
@@ -174,3 +178,67 @@ async fn load_data(col_meta: &ColumnChunkMetaData, dal: &Operator, path: &str) -
         .await?;
     Ok(bytes)
 }
+
+/// Reads the Parquet split-block bloom filter (SBBF) that writers embed
+/// per column chunk, as opposed to the bloom index stored as ordinary
+/// Parquet columns that [`load_bloom_filter_by_columns`] reads.
+pub async fn load_native_bloom_filter(
+    dal: &Operator,
+    path: &str,
+    col_meta: &ColumnChunkMetaData,
+) -> Result<Option<SplitBlockBloomFilter>> {
+    let chunk_meta = col_meta.metadata();
+    let (offset, length) = match (chunk_meta.bloom_filter_offset, chunk_meta.bloom_filter_length) {
+        (Some(offset), Some(length)) => (offset as u64, length as u64),
+        _ => return Ok(None),
+    };
+    let object = dal.object(path);
+    let bytes = object.range_read(offset..offset + length).await?;
+    Ok(Some(SplitBlockBloomFilter::from_bytes(&bytes)?))
+}
+
+/// Row-group pruning entry point: `true` means the equality predicate
+/// `column = value` cannot match anything in this column chunk, so the
+/// row group holding it can be skipped without reading its data pages.
+pub async fn column_chunk_excludes_value(
+    dal: &Operator,
+    path: &str,
+    col_meta: &ColumnChunkMetaData,
+    value: &DataValue,
+) -> Result<bool> {
+    match load_native_bloom_filter(dal, path, col_meta).await? {
+        Some(filter) => match plain_encode_for_physical_type(col_meta, value) {
+            // A value with no plain encoding for the column's physical type
+            // (e.g. a string literal compared against a numeric column) can't
+            // be hashed the way the writer hashed it, so the filter can't
+            // rule it out: nothing to prune.
+            Some(bytes) => Ok(!filter.might_contain(xxhash64(&bytes))),
+            None => Ok(false),
+        },
+        // No embedded filter: nothing to prune on.
+        None => Ok(false),
+    }
+}
+
+/// Parquet's SBBF hashes the value's *plain encoding*, which varies by the
+/// column's physical type (e.g. an `INT32` column hashes 4 bytes, not 8) —
+/// so the encoding width must come from the column, not the `DataValue`.
+fn plain_encode_for_physical_type(col_meta: &ColumnChunkMetaData, value: &DataValue) -> Option<Vec<u8>> {
+    let physical_type = col_meta.descriptor().descriptor.primitive_type.physical_type;
+    match (physical_type, value) {
+        (PhysicalType::Int32, DataValue::Int64(v)) => Some((*v as i32).to_le_bytes().to_vec()),
+        (PhysicalType::Int32, DataValue::UInt64(v)) => Some((*v as i32).to_le_bytes().to_vec()),
+        (PhysicalType::Int64, DataValue::Int64(v)) => Some(v.to_le_bytes().to_vec()),
+        (PhysicalType::Int64, DataValue::UInt64(v)) => Some((*v as i64).to_le_bytes().to_vec()),
+        (PhysicalType::Float, DataValue::Float64(v)) => Some((*v as f32).to_le_bytes().to_vec()),
+        (PhysicalType::Double, DataValue::Float64(v)) => Some(v.to_le_bytes().to_vec()),
+        (PhysicalType::ByteArray, DataValue::String(v)) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// The Parquet SBBF spec hashes with XXH64 (seed 0) — not XXH3, which is a
+/// different, incompatible algorithm.
+fn xxhash64(bytes: &[u8]) -> u64 {
+    twox_hash::XxHash64::oneshot(0, bytes)
+}
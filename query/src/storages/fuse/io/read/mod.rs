@@ -0,0 +1,21 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+mod bloom_filter_sbbf;
+mod bloom_index_reader;
+
+pub use bloom_filter_sbbf::SplitBlockBloomFilter;
+pub use bloom_index_reader::column_chunk_excludes_value;
+pub use bloom_index_reader::load_bloom_filter_by_columns;
+pub use bloom_index_reader::load_native_bloom_filter;
@@ -0,0 +1,60 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_arrow::parquet::metadata::FileMetaData;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use opendal::Operator;
+
+use crate::storages::fuse::io::read::column_chunk_excludes_value;
+
+/// An equality predicate `column = value` a scan can prune row groups with.
+pub struct EqualityPredicate {
+    pub column: String,
+    pub value: DataValue,
+}
+
+/// Scan-time row-group pruning: for each row group, probes the native
+/// Parquet bloom filter (SBBF) of every equality-predicate column, and
+/// drops the row group if any predicate's bloom filter proves its value
+/// cannot be present.
+///
+/// Returns the indices of the row groups that survive pruning.
+pub async fn prune_row_groups(
+    dal: &Operator,
+    path: &str,
+    file_meta: &FileMetaData,
+    predicates: &[EqualityPredicate],
+) -> Result<Vec<usize>> {
+    if predicates.is_empty() {
+        return Ok((0..file_meta.row_groups.len()).collect());
+    }
+
+    let mut surviving = Vec::with_capacity(file_meta.row_groups.len());
+    'row_groups: for (row_group_idx, row_group) in file_meta.row_groups.iter().enumerate() {
+        for predicate in predicates {
+            let col_meta = row_group.columns().iter().find(|c| {
+                c.descriptor().path_in_schema.first().map(String::as_str) == Some(predicate.column.as_str())
+            });
+            let Some(col_meta) = col_meta else {
+                continue;
+            };
+            if column_chunk_excludes_value(dal, path, col_meta, &predicate.value).await? {
+                continue 'row_groups;
+            }
+        }
+        surviving.push(row_group_idx);
+    }
+    Ok(surviving)
+}
@@ -0,0 +1,208 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Reader as AvroReader;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+
+/// One row of an Iceberg manifest file: a live data file plus the partition
+/// and column-statistics values a pruner can filter on before ever opening
+/// the Parquet file itself.
+#[derive(Debug, Clone)]
+pub struct DataFileEntry {
+    pub file_path: String,
+    pub record_count: i64,
+    /// Partition field name -> its value for this file, as written in the
+    /// manifest's partition tuple.
+    pub partition: HashMap<String, String>,
+    /// Column id -> lower/upper bound, used for statistics-based pruning.
+    pub lower_bounds: HashMap<i32, Vec<u8>>,
+    pub upper_bounds: HashMap<i32, Vec<u8>>,
+}
+
+/// An entry of the table's manifest list (itself an Avro file): points at
+/// one manifest file recording a batch of data files.
+#[derive(Debug, Clone)]
+pub struct ManifestList {
+    pub manifest_path: String,
+}
+
+impl ManifestList {
+    /// Reads and decodes the Avro-encoded manifest list for a snapshot.
+    pub async fn read_all(dal: &Operator, manifest_list_path: &str) -> Result<Vec<Self>> {
+        let bytes = dal.object(manifest_list_path).read().await?;
+        let reader = AvroReader::new(Cursor::new(bytes))
+            .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg manifest list: {}", e)))?;
+
+        reader
+            .map(|record| {
+                let record = record
+                    .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg manifest list record: {}", e)))?;
+                let fields = avro_record_fields(record)?;
+                Ok(Self {
+                    manifest_path: avro_string(&fields, "manifest_path")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads the data-file entries recorded in this manifest (only "added"
+    /// and "existing" entries are live; "deleted" entries are skipped).
+    pub async fn read_data_files(&self, dal: &Operator) -> Result<Vec<DataFileEntry>> {
+        let bytes = dal.object(&self.manifest_path).read().await?;
+        let reader = AvroReader::new(Cursor::new(bytes))
+            .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg manifest: {}", e)))?;
+
+        let mut files = vec![];
+        for record in reader {
+            let record = record
+                .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg manifest record: {}", e)))?;
+            let entry = avro_record_fields(record)?;
+            // status: 0 = EXISTING, 1 = ADDED, 2 = DELETED.
+            if avro_int(&entry, "status")? == 2 {
+                continue;
+            }
+            let data_file = match entry.get("data_file") {
+                Some(AvroValue::Record(fields)) => fields.iter().cloned().collect::<HashMap<_, _>>(),
+                _ => {
+                    return Err(ErrorCode::ParquetError(
+                        "Iceberg manifest entry is missing its data_file record",
+                    ))
+                }
+            };
+            files.push(DataFileEntry {
+                file_path: avro_string(&data_file, "file_path")?,
+                record_count: avro_int(&data_file, "record_count")?,
+                partition: avro_partition_record(&data_file, "partition")?,
+                lower_bounds: avro_bounds_array(&data_file, "lower_bounds")?,
+                upper_bounds: avro_bounds_array(&data_file, "upper_bounds")?,
+            });
+        }
+        Ok(files)
+    }
+}
+
+/// Drops any `DataFileEntry` whose partition/statistics can't possibly
+/// satisfy `predicate`, without opening the underlying Parquet file.
+pub fn prune_data_files(
+    files: Vec<DataFileEntry>,
+    predicate: impl Fn(&DataFileEntry) -> bool,
+) -> Vec<DataFileEntry> {
+    files.into_iter().filter(predicate).collect()
+}
+
+fn avro_record_fields(value: AvroValue) -> Result<HashMap<String, AvroValue>> {
+    match value {
+        AvroValue::Record(fields) => Ok(fields.into_iter().collect()),
+        other => Err(ErrorCode::ParquetError(format!(
+            "expected an Avro record, got {:?}",
+            other
+        ))),
+    }
+}
+
+fn avro_string(fields: &HashMap<String, AvroValue>, name: &str) -> Result<String> {
+    match fields.get(name) {
+        Some(AvroValue::String(s)) => Ok(s.clone()),
+        other => Err(ErrorCode::ParquetError(format!(
+            "Iceberg manifest field '{}' is not a string: {:?}",
+            name, other
+        ))),
+    }
+}
+
+fn avro_int(fields: &HashMap<String, AvroValue>, name: &str) -> Result<i64> {
+    match fields.get(name) {
+        Some(AvroValue::Int(v)) => Ok(*v as i64),
+        Some(AvroValue::Long(v)) => Ok(*v),
+        other => Err(ErrorCode::ParquetError(format!(
+            "Iceberg manifest field '{}' is not an integer: {:?}",
+            name, other
+        ))),
+    }
+}
+
+/// The partition tuple is written as an Avro `record` (one field per
+/// partition column, named after it), not a string->string `map` — there is
+/// no map-typed partition representation in the Iceberg manifest spec.
+fn avro_partition_record(
+    fields: &HashMap<String, AvroValue>,
+    name: &str,
+) -> Result<HashMap<String, String>> {
+    match fields.get(name) {
+        None => Ok(HashMap::new()),
+        Some(AvroValue::Record(partition_fields)) => Ok(partition_fields
+            .iter()
+            .map(|(k, v)| (k.clone(), avro_value_to_string(v)))
+            .collect()),
+        Some(other) => Err(ErrorCode::ParquetError(format!(
+            "Iceberg manifest field '{}' is not a partition record: {:?}",
+            name, other
+        ))),
+    }
+}
+
+/// `lower_bounds`/`upper_bounds` are `map<int, bytes>` fields, but Avro has
+/// no native map-with-non-string-keys type: the spec encodes them as an
+/// `array` of `{key: int, value: bytes}` records instead of `AvroValue::Map`.
+fn avro_bounds_array(
+    fields: &HashMap<String, AvroValue>,
+    name: &str,
+) -> Result<HashMap<i32, Vec<u8>>> {
+    match fields.get(name) {
+        None => Ok(HashMap::new()),
+        Some(AvroValue::Array(items)) => items
+            .iter()
+            .map(|item| {
+                let entry = avro_record_fields(item.clone())?;
+                let key = avro_int(&entry, "key")? as i32;
+                let value = match entry.get("value") {
+                    Some(AvroValue::Bytes(b)) => b.clone(),
+                    other => {
+                        return Err(ErrorCode::ParquetError(format!(
+                            "Iceberg bounds entry '{}' value is not bytes: {:?}",
+                            name, other
+                        )))
+                    }
+                };
+                Ok((key, value))
+            })
+            .collect(),
+        Some(other) => Err(ErrorCode::ParquetError(format!(
+            "Iceberg manifest field '{}' is not a bounds array: {:?}",
+            name, other
+        ))),
+    }
+}
+
+fn avro_value_to_string(value: &AvroValue) -> String {
+    match value {
+        AvroValue::Null => "NULL".to_string(),
+        AvroValue::Boolean(v) => v.to_string(),
+        AvroValue::Int(v) => v.to_string(),
+        AvroValue::Long(v) => v.to_string(),
+        AvroValue::Float(v) => v.to_string(),
+        AvroValue::Double(v) => v.to_string(),
+        AvroValue::String(v) => v.clone(),
+        AvroValue::Bytes(v) | AvroValue::Fixed(_, v) => String::from_utf8_lossy(v).into_owned(),
+        // Optional partition fields are `["null", <type>]` unions.
+        AvroValue::Union(_, inner) => avro_value_to_string(inner),
+        other => format!("{:?}", other),
+    }
+}
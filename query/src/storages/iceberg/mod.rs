@@ -0,0 +1,25 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Table engine for externally-managed [Apache Iceberg](https://iceberg.apache.org)
+//! tables: reads the table's JSON metadata and Avro-encoded manifests to find
+//! the live data files, then streams them through the same `opendal` +
+//! Parquet reading path the fuse bloom index reader uses.
+
+mod manifest;
+mod table;
+
+pub use manifest::DataFileEntry;
+pub use manifest::ManifestList;
+pub use table::IcebergTable;
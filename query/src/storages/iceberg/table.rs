@@ -0,0 +1,215 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::column_iter_to_arrays;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_metadata_async;
+use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+use serde::Deserialize;
+
+use super::manifest::prune_data_files;
+use super::manifest::DataFileEntry;
+use super::manifest::ManifestList;
+use crate::storages::Table;
+use crate::storages::TableCreateOptions;
+
+#[derive(Debug, Deserialize)]
+struct TableMetadata {
+    #[serde(rename = "current-snapshot-id")]
+    current_snapshot_id: i64,
+    snapshots: Vec<SnapshotMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotMetadata {
+    #[serde(rename = "snapshot-id")]
+    snapshot_id: i64,
+    #[serde(rename = "manifest-list")]
+    manifest_list: String,
+}
+
+/// A table backed by an externally-managed Apache Iceberg table: the engine
+/// never writes to `table_location`, it only resolves the current snapshot
+/// and streams the data files it points to.
+pub struct IcebergTable {
+    table_name: String,
+    dal: Operator,
+    table_location: String,
+}
+
+impl IcebergTable {
+    pub fn create(table_name: String, dal: Operator, table_location: String) -> Self {
+        Self {
+            table_name,
+            dal,
+            table_location,
+        }
+    }
+
+    /// [`crate::storages::StorageFactory`] creator for the `ICEBERG` engine.
+    pub fn try_create(options: TableCreateOptions) -> Result<Arc<dyn Table>> {
+        Ok(Arc::new(Self::create(
+            options.table_name,
+            options.dal,
+            options.table_location,
+        )))
+    }
+
+    /// Resolves the `metadata/*.metadata.json` file that is currently
+    /// authoritative for this table.
+    ///
+    /// There's no separate catalog service backing this engine, so it
+    /// follows the same convention Iceberg's own `HadoopTableOperations`
+    /// uses for filesystem-only tables: `metadata/version-hint.text` holds
+    /// the current version number, and the metadata file for version `N` is
+    /// `metadata/vN.metadata.json`.
+    async fn resolve_current_metadata_file(&self) -> Result<String> {
+        let base = self.table_location.trim_end_matches('/');
+        let hint_path = format!("{}/metadata/version-hint.text", base);
+        let hint = self.dal.object(&hint_path).read().await.map_err(|e| {
+            ErrorCode::ParquetError(format!(
+                "failed to read Iceberg version hint '{}': {}",
+                hint_path, e
+            ))
+        })?;
+        let version = std::str::from_utf8(&hint)
+            .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg version hint: {}", e)))?
+            .trim();
+        Ok(format!("{}/metadata/v{}.metadata.json", base, version))
+    }
+
+    /// Reads `table_location/metadata/*.metadata.json` (the caller passes
+    /// the exact metadata file, resolved via [`Self::resolve_current_metadata_file`])
+    /// and resolves it down to the list of live, and possibly pruned, data
+    /// files.
+    pub async fn current_data_files(
+        &self,
+        metadata_file: &str,
+        predicate: impl Fn(&DataFileEntry) -> bool,
+    ) -> Result<Vec<DataFileEntry>> {
+        let metadata = self.read_table_metadata(metadata_file).await?;
+        let snapshot = metadata
+            .snapshots
+            .iter()
+            .find(|s| s.snapshot_id == metadata.current_snapshot_id)
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Iceberg table metadata is missing its current snapshot {}",
+                    metadata.current_snapshot_id
+                ))
+            })?;
+
+        let manifest_lists = ManifestList::read_all(&self.dal, &snapshot.manifest_list).await?;
+        let mut files = vec![];
+        for manifest in manifest_lists {
+            files.extend(manifest.read_data_files(&self.dal).await?);
+        }
+        Ok(prune_data_files(files, predicate))
+    }
+
+    async fn read_table_metadata(&self, metadata_file: &str) -> Result<TableMetadata> {
+        let object = self.dal.object(metadata_file);
+        let bytes = object.read().await?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| ErrorCode::ParquetError(format!("invalid Iceberg table metadata: {}", e)))
+    }
+
+    /// Infers a data file's Databend schema from its Parquet footer — the
+    /// same schema every data file in the table is expected to share.
+    async fn infer_file_schema(&self, file_path: &str) -> Result<DataSchemaRef> {
+        let object = self.dal.object(file_path);
+        let mut reader = object.seekable_reader(0..);
+        let file_meta = read_metadata_async(&mut reader).await?;
+        let arrow_schema = infer_schema(&file_meta)?;
+        Ok(Arc::new(common_datavalues::DataSchema::try_from(&arrow_schema)?))
+    }
+
+    /// Streams one surviving data file as `DataBlock`s, reusing the same
+    /// async Parquet reading path the fuse bloom index loader uses.
+    pub async fn read_data_file(&self, file: &DataFileEntry) -> Result<Vec<DataBlock>> {
+        let object = self.dal.object(&file.file_path);
+        let mut reader = object.seekable_reader(0..);
+        let file_meta = read_metadata_async(&mut reader).await?;
+        let arrow_schema = infer_schema(&file_meta)?;
+        let schema = Arc::new(common_datavalues::DataSchema::try_from(&arrow_schema)?);
+
+        let mut blocks = vec![];
+        for row_group in &file_meta.row_groups {
+            let column_descriptors = file_meta.schema_descr.columns();
+            let mut columns_array_iter = vec![];
+            for (col_idx, _) in column_descriptors.iter().enumerate() {
+                let field = arrow_schema.fields[col_idx].clone();
+                let column_meta = &row_group.columns()[col_idx];
+                let pages = common_arrow::parquet::read::get_page_iterator(
+                    column_meta,
+                    object.clone_reader(),
+                    vec![],
+                    vec![],
+                )?;
+                let decompressor = common_arrow::parquet::read::BasicDecompressor::new(pages, vec![]);
+                columns_array_iter.push(column_iter_to_arrays(
+                    vec![decompressor],
+                    vec![&column_descriptors[col_idx].descriptor.primitive_type],
+                    field,
+                    Some(row_group.num_rows()),
+                )?);
+            }
+            let mut deserializer =
+                RowGroupDeserializer::new(columns_array_iter, row_group.num_rows(), None);
+            while let Some(chunk) = deserializer.next() {
+                blocks.push(DataBlock::from_chunk(&schema, &chunk?)?);
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for IcebergTable {
+    fn name(&self) -> &str {
+        &self.table_name
+    }
+
+    fn engine(&self) -> &str {
+        "ICEBERG"
+    }
+
+    async fn schema(&self) -> Result<DataSchemaRef> {
+        let metadata_file = self.resolve_current_metadata_file().await?;
+        let files = self.current_data_files(&metadata_file, |_| true).await?;
+        let file = files.first().ok_or_else(|| {
+            ErrorCode::LogicalError(
+                "Iceberg table has no live data files to infer a schema from".to_string(),
+            )
+        })?;
+        self.infer_file_schema(&file.file_path).await
+    }
+
+    async fn read(&self) -> Result<Vec<DataBlock>> {
+        let metadata_file = self.resolve_current_metadata_file().await?;
+        let files = self.current_data_files(&metadata_file, |_| true).await?;
+        let mut blocks = vec![];
+        for file in &files {
+            blocks.extend(self.read_data_file(file).await?);
+        }
+        Ok(blocks)
+    }
+}
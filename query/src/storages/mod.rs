@@ -0,0 +1,59 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+pub mod fuse;
+pub mod iceberg;
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+
+pub use iceberg::IcebergTable;
+
+/// A table engine: something that can describe its schema and stream its
+/// data. `ICEBERG` (read-only, over an externally-managed table) is the only
+/// engine in this crate that implements it; the `fuse` module provides
+/// reading and pruning helpers but no table.
+#[async_trait::async_trait]
+pub trait Table: Send + Sync {
+    fn name(&self) -> &str;
+    fn engine(&self) -> &str;
+    /// Async because an externally-managed engine like `ICEBERG` has no
+    /// locally-cached schema to hand back
+    /// synchronously — resolving it means reading the table's metadata.
+    async fn schema(&self) -> Result<DataSchemaRef>;
+    async fn read(&self) -> Result<Vec<DataBlock>>;
+}
+
+/// Where a table engine is created from: the catalog-resolved location of
+/// the table plus the `opendal::Operator` to read it through.
+pub struct TableCreateOptions {
+    pub table_name: String,
+    pub table_location: String,
+    pub dal: opendal::Operator,
+}
+
+/// Registers every built-in table engine's creator under its `CREATE TABLE
+/// ... ENGINE = '<name>'` name, the same way `FunctionFactory` registers
+/// scalar/aggregate function creators.
+pub struct StorageFactory;
+
+impl StorageFactory {
+    pub fn engine_creators() -> Vec<(&'static str, fn(TableCreateOptions) -> Result<Arc<dyn Table>>)>
+    {
+        vec![("ICEBERG", IcebergTable::try_create)]
+    }
+}
@@ -66,6 +66,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // RPC API service.
+    //
+    // Note: this does not yet serve a Substrait plan exchange endpoint.
+    // `common_substrait` only provides the producer/consumer that would sit
+    // behind such an endpoint; wiring it into `RpcService` is still open.
     {
         let conf = cfg.clone();
         tokio::spawn(async move {
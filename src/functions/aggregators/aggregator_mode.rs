@@ -0,0 +1,62 @@
+// Copyright 2020 The FuseQuery Authors.
+//
+// Code is licensed under AGPL License, Version 3.0.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::datablocks::DataBlock;
+use crate::datavalues::{DataColumnarValue, DataSchema, DataType, DataValue};
+use crate::error::FuseQueryResult;
+use crate::functions::Function;
+
+#[derive(Clone)]
+pub struct AggregatorModeFunction {
+    arg: Box<Function>,
+    counts: HashMap<DataValue, usize>,
+}
+
+impl AggregatorModeFunction {
+    pub fn try_create(arg: Function) -> FuseQueryResult<Function> {
+        Ok(Function::Aggregator(
+            crate::functions::AggregatorFunction::Mode(Self {
+                arg: Box::new(arg),
+                counts: HashMap::new(),
+            }),
+        ))
+    }
+
+    pub fn return_type(&self, input_schema: &DataSchema) -> FuseQueryResult<DataType> {
+        self.arg.return_type(input_schema)
+    }
+
+    pub fn nullable(&self, _input_schema: &DataSchema) -> FuseQueryResult<bool> {
+        Ok(true)
+    }
+
+    pub fn eval(&mut self, block: &DataBlock) -> FuseQueryResult<()> {
+        self.arg.eval(block)?;
+        if let DataColumnarValue::Array(array) = self.arg.result()? {
+            for v in array.iter().flatten() {
+                *self.counts.entry(v).or_insert(0) += 1;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn result(&self) -> FuseQueryResult<DataColumnarValue> {
+        // Most frequent value, ties broken by the smallest value.
+        let mode = self
+            .counts
+            .iter()
+            .max_by(|(lv, lc), (rv, rc)| lc.cmp(rc).then(rv.cmp(lv)))
+            .map(|(v, _)| v.clone());
+        Ok(DataColumnarValue::Scalar(mode))
+    }
+}
+
+impl fmt::Display for AggregatorModeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MODE({})", self.arg)
+    }
+}
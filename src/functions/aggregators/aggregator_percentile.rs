@@ -0,0 +1,153 @@
+// Copyright 2020 The FuseQuery Authors.
+//
+// Code is licensed under AGPL License, Version 3.0.
+
+use std::fmt;
+
+use crate::datablocks::DataBlock;
+use crate::datavalues::{DataColumnarValue, DataSchema, DataType, DataValue};
+use crate::error::{FuseQueryError, FuseQueryResult};
+use crate::functions::Function;
+
+/// Shared per-block extraction used by both PERCENTILE_CONT and
+/// PERCENTILE_DISC: collects the non-null values of the `ORDER BY` argument
+/// for this block. The caller is responsible for (re-)sorting the full,
+/// accumulated buffer — sorting here too would just sort the same values
+/// twice once later blocks are merged in.
+fn block_values(arg: &mut Function, block: &DataBlock) -> FuseQueryResult<Vec<f64>> {
+    arg.eval(block)?;
+    let values = match arg.result()? {
+        DataColumnarValue::Array(array) => array
+            .iter()
+            .filter_map(|v| v.as_ref().map(DataValue::as_f64).transpose().ok().flatten())
+            .collect::<Vec<_>>(),
+        DataColumnarValue::Scalar(v) => match v {
+            Some(v) => vec![v.as_f64()?],
+            None => vec![],
+        },
+    };
+    Ok(values)
+}
+
+fn check_fraction(p: f64) -> FuseQueryResult<()> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err(FuseQueryError::Internal(format!(
+            "PERCENTILE fraction must be between 0 and 1, got {}",
+            p
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct AggregatorPercentileContFunction {
+    p: f64,
+    arg: Box<Function>,
+    values: Vec<f64>,
+}
+
+impl AggregatorPercentileContFunction {
+    pub fn try_create(p: f64, arg: Function) -> FuseQueryResult<Function> {
+        check_fraction(p)?;
+        Ok(Function::Aggregator(
+            crate::functions::AggregatorFunction::PercentileCont(Self {
+                p,
+                arg: Box::new(arg),
+                values: vec![],
+            }),
+        ))
+    }
+
+    pub fn return_type(&self, _input_schema: &DataSchema) -> FuseQueryResult<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    pub fn nullable(&self, _input_schema: &DataSchema) -> FuseQueryResult<bool> {
+        Ok(true)
+    }
+
+    pub fn eval(&mut self, block: &DataBlock) -> FuseQueryResult<()> {
+        self.values.extend(block_values(&mut self.arg, block)?);
+        // `f64::partial_cmp` returns `None` for NaN, which panics on
+        // `.unwrap()`; `total_cmp` gives NaN a well-defined (if somewhat
+        // arbitrary) place in the order instead of crashing the whole batch
+        // over one bad row.
+        self.values.sort_by(|a, b| a.total_cmp(b));
+        Ok(())
+    }
+
+    pub fn result(&self) -> FuseQueryResult<DataColumnarValue> {
+        let n = self.values.len();
+        let result = if n == 0 {
+            None
+        } else if n == 1 {
+            Some(self.values[0])
+        } else {
+            let rank = self.p * (n - 1) as f64;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            let frac = rank - lo as f64;
+            Some(self.values[lo] + frac * (self.values[hi] - self.values[lo]))
+        };
+        Ok(DataColumnarValue::Scalar(result.map(DataValue::Float64)))
+    }
+}
+
+impl fmt::Display for AggregatorPercentileContFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PERCENTILE_CONT({}) WITHIN GROUP (ORDER BY {})", self.p, self.arg)
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregatorPercentileDiscFunction {
+    p: f64,
+    arg: Box<Function>,
+    values: Vec<f64>,
+}
+
+impl AggregatorPercentileDiscFunction {
+    pub fn try_create(p: f64, arg: Function) -> FuseQueryResult<Function> {
+        check_fraction(p)?;
+        Ok(Function::Aggregator(
+            crate::functions::AggregatorFunction::PercentileDisc(Self {
+                p,
+                arg: Box::new(arg),
+                values: vec![],
+            }),
+        ))
+    }
+
+    pub fn return_type(&self, _input_schema: &DataSchema) -> FuseQueryResult<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    pub fn nullable(&self, _input_schema: &DataSchema) -> FuseQueryResult<bool> {
+        Ok(true)
+    }
+
+    pub fn eval(&mut self, block: &DataBlock) -> FuseQueryResult<()> {
+        self.values.extend(block_values(&mut self.arg, block)?);
+        self.values.sort_by(|a, b| a.total_cmp(b));
+        Ok(())
+    }
+
+    pub fn result(&self) -> FuseQueryResult<DataColumnarValue> {
+        let n = self.values.len();
+        let result = if n == 0 {
+            None
+        } else {
+            let idx = ((self.p * n as f64).ceil() as usize)
+                .saturating_sub(1)
+                .min(n - 1);
+            Some(self.values[idx])
+        };
+        Ok(DataColumnarValue::Scalar(result.map(DataValue::Float64)))
+    }
+}
+
+impl fmt::Display for AggregatorPercentileDiscFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PERCENTILE_DISC({}) WITHIN GROUP (ORDER BY {})", self.p, self.arg)
+    }
+}
@@ -33,7 +33,7 @@ impl Function {
             Function::Arithmetic(v) => v.return_type(input_schema),
             Function::Comparison(v) => v.return_type(input_schema),
             Function::Logic(v) => v.return_type(input_schema),
-            Function::Aggregator(v) => v.return_type(),
+            Function::Aggregator(v) => v.return_type(input_schema),
         }
     }
 
@@ -0,0 +1,67 @@
+// Copyright 2020 The FuseQuery Authors.
+//
+// Code is licensed under AGPL License, Version 3.0.
+
+use std::fmt;
+
+use crate::datablocks::DataBlock;
+use crate::datavalues::{DataColumnarValue, DataSchema, DataType};
+use crate::error::FuseQueryResult;
+use crate::functions::aggregators::aggregator_mode::AggregatorModeFunction;
+use crate::functions::aggregators::aggregator_percentile::{
+    AggregatorPercentileContFunction, AggregatorPercentileDiscFunction,
+};
+
+/// Ordered-set and frequency aggregates, i.e. aggregates whose result
+/// depends on the whole sorted group rather than on a single running
+/// accumulator (`WITHIN GROUP (ORDER BY ...)`).
+#[derive(Clone)]
+pub enum AggregatorFunction {
+    PercentileCont(AggregatorPercentileContFunction),
+    PercentileDisc(AggregatorPercentileDiscFunction),
+    Mode(AggregatorModeFunction),
+}
+
+impl AggregatorFunction {
+    pub fn return_type(&self, input_schema: &DataSchema) -> FuseQueryResult<DataType> {
+        match self {
+            AggregatorFunction::PercentileCont(v) => v.return_type(input_schema),
+            AggregatorFunction::PercentileDisc(v) => v.return_type(input_schema),
+            AggregatorFunction::Mode(v) => v.return_type(input_schema),
+        }
+    }
+
+    pub fn nullable(&self, input_schema: &DataSchema) -> FuseQueryResult<bool> {
+        match self {
+            AggregatorFunction::PercentileCont(v) => v.nullable(input_schema),
+            AggregatorFunction::PercentileDisc(v) => v.nullable(input_schema),
+            AggregatorFunction::Mode(v) => v.nullable(input_schema),
+        }
+    }
+
+    pub fn eval(&mut self, block: &DataBlock) -> FuseQueryResult<()> {
+        match self {
+            AggregatorFunction::PercentileCont(v) => v.eval(block),
+            AggregatorFunction::PercentileDisc(v) => v.eval(block),
+            AggregatorFunction::Mode(v) => v.eval(block),
+        }
+    }
+
+    pub fn result(&self) -> FuseQueryResult<DataColumnarValue> {
+        match self {
+            AggregatorFunction::PercentileCont(v) => v.result(),
+            AggregatorFunction::PercentileDisc(v) => v.result(),
+            AggregatorFunction::Mode(v) => v.result(),
+        }
+    }
+}
+
+impl fmt::Display for AggregatorFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregatorFunction::PercentileCont(v) => write!(f, "{}", v),
+            AggregatorFunction::PercentileDisc(v) => write!(f, "{}", v),
+            AggregatorFunction::Mode(v) => write!(f, "{}", v),
+        }
+    }
+}